@@ -0,0 +1,244 @@
+use crate::traits::*;
+use crate::{Any, Class, Error, Header, Length, ParseResult, Result, SerializeResult, Tag};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+mod set_of;
+
+pub use set_of::*;
+
+/// A constructed SET, i.e. an unordered collection of distinct types.
+///
+/// `Set` mirrors [`Sequence`](crate::Sequence) in every respect except DER
+/// canonical ordering (X.690 §11.6): when building one with
+/// [`Set::from_iter_to_der`], the encoded components are sorted by
+/// ascending `(class, tag number)` before being concatenated, and
+/// [`Set::check_constraints`] rejects content whose components are not
+/// already in that order. BER has no such ordering requirement, so
+/// `ber_iter`/`ber_sequence_of`-style parsing (see
+/// [`Sequence`](crate::Sequence)) accepts any order; only the DER path
+/// enforces canonical ordering.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Set<'a> {
+    pub content: Cow<'a, [u8]>,
+}
+
+impl<'a> Set<'a> {
+    pub const fn new(content: Cow<'a, [u8]>) -> Self {
+        Set { content }
+    }
+
+    #[inline]
+    pub fn into_content(self) -> Cow<'a, [u8]> {
+        self.content
+    }
+
+    pub fn and_then<U, F>(self, op: F) -> ParseResult<'a, U>
+    where
+        F: FnOnce(Cow<'a, [u8]>) -> ParseResult<U>,
+    {
+        op(self.content)
+    }
+
+    pub fn parse<F, T>(&'a self, mut f: F) -> ParseResult<'a, T>
+    where
+        F: FnMut(&'a [u8]) -> ParseResult<'a, T>,
+    {
+        let input: &[u8] = &self.content;
+        f(input)
+    }
+
+    pub fn parse_ref<F, T>(self, mut f: F) -> ParseResult<'a, T>
+    where
+        F: FnMut(&'a [u8]) -> ParseResult<'a, T>,
+    {
+        match self.content {
+            Cow::Borrowed(b) => f(b),
+            _ => Err(nom::Err::Failure(Error::LifetimeError)),
+        }
+    }
+
+    pub fn ber_iter<T>(&'a self) -> crate::SequenceIterator<'a, T, BerParser>
+    where
+        T: FromBer<'a>,
+    {
+        crate::SequenceIterator::new(&self.content)
+    }
+
+    pub fn der_iter<T>(&'a self) -> crate::SequenceIterator<'a, T, DerParser>
+    where
+        T: FromDer<'a>,
+    {
+        crate::SequenceIterator::new(&self.content)
+    }
+
+    pub fn ber_set_of<T>(&'a self) -> Result<Vec<T>>
+    where
+        T: FromBer<'a>,
+    {
+        self.ber_iter().collect()
+    }
+
+    pub fn der_set_of<T>(&'a self) -> Result<Vec<T>>
+    where
+        T: FromDer<'a>,
+    {
+        self.der_iter().collect()
+    }
+
+    /// Build a DER-encoded `Set` from an iterator of components, sorting
+    /// them into canonical order (ascending class, then ascending tag
+    /// number) before concatenating, per X.690 §11.6.
+    ///
+    /// This is the right constructor for a structured SET whose members
+    /// may be of different ASN.1 types (e.g. a SET of distinct, tagged
+    /// fields). The sort key is read back from each component's own
+    /// encoded header rather than from a single static `Tagged::TAG`, so
+    /// it works whether `T` is one concrete type or an enum/`Any` whose
+    /// instances carry different tags. For a homogeneous, order-
+    /// insensitive SET OF, [`SetOf::from_iter_to_der`] sorts by a
+    /// different key (whole-element byte comparison, not `(class, tag)`),
+    /// which is the correct canonical order for that case instead.
+    pub fn from_iter_to_der<T, IT>(it: IT) -> SerializeResult<Self>
+    where
+        IT: Iterator<Item = T>,
+        T: ToDer,
+    {
+        let mut encoded = it
+            .map(|item| -> SerializeResult<((u32, u32), Vec<u8>)> {
+                let bytes = item.to_der_vec()?;
+                let (_, header) = Header::parse_der(&bytes).map_err(Into::<Error>::into)?;
+                Ok(((header.class() as u32, header.tag().0), bytes))
+            })
+            .collect::<SerializeResult<Vec<_>>>()?;
+        encoded.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+        let mut content = Vec::new();
+        for (_, bytes) in encoded {
+            content.extend_from_slice(&bytes);
+        }
+        Ok(Set {
+            content: Cow::Owned(content),
+        })
+    }
+}
+
+impl<'a> ToStatic for Set<'a> {
+    type Owned = Set<'static>;
+
+    fn to_static(&self) -> Self::Owned {
+        Set {
+            content: Cow::Owned(self.content.to_vec()),
+        }
+    }
+}
+
+impl<'a> AsRef<[u8]> for Set<'a> {
+    fn as_ref(&self) -> &[u8] {
+        &self.content
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for Set<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Set<'a>> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        Ok(Set {
+            content: any.into_cow(),
+        })
+    }
+}
+
+impl<'a> CheckDerConstraints for Set<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        // X.690 §11.6: the encodings of a SET's components, each taken as a
+        // whole, must appear in ascending order of their tag (class, then
+        // tag number). Reject content that is not already sorted this way;
+        // BER (outside this DER-specific check) accepts any order.
+        any.header.assert_constructed()?;
+        let mut prev_key: Option<(u32, u32)> = None;
+        for item in crate::SequenceIterator::<Any, DerParser>::new(&any.data) {
+            let item = item?;
+            let key = (item.header.class() as u32, item.tag().0);
+            if let Some(prev) = prev_key {
+                if prev > key {
+                    return Err(Error::DerConstraintFailed);
+                }
+            }
+            prev_key = Some(key);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Tagged for Set<'a> {
+    const TAG: Tag = Tag::Set;
+}
+
+impl ToDer for Set<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = self.content.len();
+        if sz < 127 {
+            // 1 (class+tag) + 1 (length) + len
+            Ok(2 + sz)
+        } else {
+            // 1 (class+tag) + n (length) + len
+            let n = Length::Definite(sz).to_der_len()?;
+            Ok(1 + n + sz)
+        }
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let header = Header::new(
+            Class::Universal,
+            1,
+            Self::TAG,
+            Length::Definite(self.content.len()),
+        );
+        header.write_der_header(writer).map_err(Into::into)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(&self.content).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn any_from_der(bytes: &'static [u8]) -> Any<'static> {
+        let (_, any) = Any::from_der(bytes).expect("from_der");
+        any
+    }
+
+    #[test]
+    fn from_iter_to_der_sorts_by_class_and_tag() {
+        let octet_string = any_from_der(&[0x04, 0x01, 0xaa]);
+        let integer = any_from_der(&[0x02, 0x01, 0x01]);
+        // Fed in reverse of canonical order; `from_iter_to_der` must still
+        // place INTEGER (tag 2) before OCTET STRING (tag 4).
+        let set = Set::from_iter_to_der(vec![octet_string, integer].into_iter())
+            .expect("from_iter_to_der");
+        let mut it = crate::SequenceIterator::<Any, DerParser>::new(&set.content);
+        let first = it.next().expect("one item").expect("valid item");
+        assert_eq!(first.tag(), Tag::Integer);
+    }
+
+    #[test]
+    fn check_constraints_rejects_unsorted_set() {
+        // SET { OCTET STRING, INTEGER } -- wrong order per X.690 §11.6.
+        let der: &[u8] = &[0x31, 0x06, 0x04, 0x01, 0xaa, 0x02, 0x01, 0x01];
+        let (_, any) = Any::from_der(der).expect("from_der");
+        assert!(Set::check_constraints(&any).is_err());
+    }
+
+    #[test]
+    fn check_constraints_accepts_sorted_set() {
+        // SET { INTEGER, OCTET STRING } -- correct canonical order.
+        let der: &[u8] = &[0x31, 0x06, 0x02, 0x01, 0x01, 0x04, 0x01, 0xaa];
+        let (_, any) = Any::from_der(der).expect("from_der");
+        assert!(Set::check_constraints(&any).is_ok());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::traits::*;
+use crate::{Any, Class, Error, Header, Length, ParseResult, Result, SerializeResult, Tag};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// A SET OF: a constructed SET whose elements are all of the same ASN.1
+/// type, used when element order carries no meaning (X.690 §8.12).
+///
+/// `SetOf` has the same shape and BER-side API as [`Set`], but its DER
+/// canonicalization rule differs: rather than ordering distinct structured
+/// components by ascending tag, [`SetOf::check_constraints`] and
+/// [`SetOf::from_iter_to_der`] order *whole encoded elements* by ascending
+/// lexicographic octet comparison, where a shorter encoding that is a
+/// prefix of a longer one sorts first (X.690 §11.6). This is what CMS
+/// `SignerInfos` and X.509 RDN attribute SET OFs require for a verifiable
+/// canonical encoding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetOf<'a> {
+    pub content: Cow<'a, [u8]>,
+}
+
+impl<'a> SetOf<'a> {
+    pub const fn new(content: Cow<'a, [u8]>) -> Self {
+        SetOf { content }
+    }
+
+    #[inline]
+    pub fn into_content(self) -> Cow<'a, [u8]> {
+        self.content
+    }
+
+    pub fn ber_iter<T>(&'a self) -> crate::SequenceIterator<'a, T, BerParser>
+    where
+        T: FromBer<'a>,
+    {
+        crate::SequenceIterator::new(&self.content)
+    }
+
+    pub fn der_iter<T>(&'a self) -> crate::SequenceIterator<'a, T, DerParser>
+    where
+        T: FromDer<'a>,
+    {
+        crate::SequenceIterator::new(&self.content)
+    }
+
+    pub fn ber_set_of<T>(&'a self) -> Result<Vec<T>>
+    where
+        T: FromBer<'a>,
+    {
+        self.ber_iter().collect()
+    }
+
+    pub fn der_set_of<T>(&'a self) -> Result<Vec<T>>
+    where
+        T: FromDer<'a>,
+    {
+        self.der_iter().collect()
+    }
+
+    /// Build a canonical DER SET OF from an iterator of same-typed
+    /// elements: each element is fully DER-encoded on its own, then the
+    /// resulting byte strings are sorted by ascending lexicographic octet
+    /// comparison (a byte string that is a strict prefix of another sorts
+    /// first) before being concatenated, per X.690 §11.6.
+    pub fn from_iter_to_der<T, IT>(it: IT) -> SerializeResult<Self>
+    where
+        IT: Iterator<Item = T>,
+        T: ToDer,
+    {
+        let mut encoded = it
+            .map(|item| item.to_der_vec())
+            .collect::<SerializeResult<Vec<Vec<u8>>>>()?;
+        // `Vec<u8>`'s lexicographic `Ord` already treats a shorter prefix as
+        // smaller, which is exactly the ordering X.690 §11.6 requires.
+        encoded.sort();
+        let mut content = Vec::new();
+        for item in encoded {
+            content.extend_from_slice(&item);
+        }
+        Ok(SetOf {
+            content: Cow::Owned(content),
+        })
+    }
+}
+
+impl<'a> ToStatic for SetOf<'a> {
+    type Owned = SetOf<'static>;
+
+    fn to_static(&self) -> Self::Owned {
+        SetOf {
+            content: Cow::Owned(self.content.to_vec()),
+        }
+    }
+}
+
+impl<'a> AsRef<[u8]> for SetOf<'a> {
+    fn as_ref(&self) -> &[u8] {
+        &self.content
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for SetOf<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<SetOf<'a>> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        Ok(SetOf {
+            content: any.into_cow(),
+        })
+    }
+}
+
+impl<'a> CheckDerConstraints for SetOf<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.header.assert_constructed()?;
+        let mut prev: Option<Vec<u8>> = None;
+        for item in crate::SequenceIterator::<Any, DerParser>::new(&any.data) {
+            let item = item?;
+            let encoded = item.to_der_vec()?;
+            if let Some(ref prev) = prev {
+                if prev > &encoded {
+                    return Err(Error::DerConstraintFailed);
+                }
+            }
+            prev = Some(encoded);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Tagged for SetOf<'a> {
+    const TAG: Tag = Tag::Set;
+}
+
+impl ToDer for SetOf<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = self.content.len();
+        if sz < 127 {
+            // 1 (class+tag) + 1 (length) + len
+            Ok(2 + sz)
+        } else {
+            // 1 (class+tag) + n (length) + len
+            let n = Length::Definite(sz).to_der_len()?;
+            Ok(1 + n + sz)
+        }
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let header = Header::new(
+            Class::Universal,
+            1,
+            Self::TAG,
+            Length::Definite(self.content.len()),
+        );
+        header.write_der_header(writer).map_err(Into::into)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(&self.content).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn any_from_der(bytes: &'static [u8]) -> Any<'static> {
+        let (_, any) = Any::from_der(bytes).expect("from_der");
+        any
+    }
+
+    #[test]
+    fn from_iter_to_der_sorts_by_encoded_bytes() {
+        let later = any_from_der(&[0x02, 0x01, 0x02]);
+        let earlier = any_from_der(&[0x02, 0x01, 0x01]);
+        let set_of = SetOf::from_iter_to_der(vec![later, earlier].into_iter())
+            .expect("from_iter_to_der");
+        assert_eq!(&set_of.content[..], &[0x02, 0x01, 0x01, 0x02, 0x01, 0x02][..]);
+    }
+
+    #[test]
+    fn check_constraints_rejects_unsorted_elements() {
+        let der: &[u8] = &[0x31, 0x06, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01];
+        let (_, any) = Any::from_der(der).expect("from_der");
+        assert!(SetOf::check_constraints(&any).is_err());
+    }
+
+    #[test]
+    fn check_constraints_accepts_sorted_elements() {
+        let der: &[u8] = &[0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02];
+        let (_, any) = Any::from_der(der).expect("from_der");
+        assert!(SetOf::check_constraints(&any).is_ok());
+    }
+}
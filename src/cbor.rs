@@ -0,0 +1,398 @@
+//! CBOR bridge: map a parsed ASN.1 tree to and from the CBOR data model
+//! (RFC 8949), so ASN.1-encoded data can be inspected or transported
+//! through generic CBOR tooling.
+//!
+//! Mapping:
+//! - SEQUENCE / SEQUENCE OF -> CBOR array
+//! - SET / SET OF -> CBOR array, tagged with [`SET_TAG`] so the reverse
+//!   direction can tell it apart from a SEQUENCE
+//! - `Utf8String` -> CBOR text string
+//! - OCTET STRING -> CBOR byte string (the content of an element not
+//!   listed here is also carried as a byte string, inside the
+//!   tag-preserving wrapper described below)
+//! - INTEGER -> CBOR integer (or bignum byte string, tagged per RFC 8949
+//!   §3.4.3, for values wider than 64 bits)
+//! - OBJECT IDENTIFIER -> [`OID_TAG`] wrapping the dotted-decimal string
+//! - BOOLEAN -> CBOR `true`/`false`; NULL -> CBOR `null`
+//!
+//! Any element whose ASN.1 tag/class is not one of the above (e.g. an
+//! implicitly-tagged context-specific element) is still round-trippable:
+//! its original class, constructed bit, and tag number are all preserved
+//! with [`TAG_PRESERVE_TAG`] so `cbor_to_der` can reconstruct the exact
+//! original encoding rather than guessing a universal type for it.
+//!
+//! Only compiled when the `cbor` feature is enabled.
+
+use crate::{Any, Class, Error, Header, Length, SerializeResult, Tag};
+use serde_cbor::Value;
+use std::convert::TryFrom;
+
+/// CBOR tag (in the unassigned, application-specific range) marking an
+/// array that represents a SET/SET OF rather than a SEQUENCE.
+pub const SET_TAG: u64 = 55800;
+
+/// CBOR tag marking a text string as an OBJECT IDENTIFIER in dotted form.
+pub const OID_TAG: u64 = 111;
+
+/// CBOR tag wrapping a `[class, constructed, tag_number, value]` array for
+/// an element whose ASN.1 tag/class this module does not map to a native
+/// CBOR type, so the original class, constructed bit, and tag number all
+/// survive the round trip.
+pub const TAG_PRESERVE_TAG: u64 = 55799;
+
+/// Convert a parsed ASN.1 element into the CBOR data model.
+pub fn any_to_cbor(any: &Any) -> Value {
+    match (any.header.class(), any.tag()) {
+        (Class::Universal, Tag::Boolean) => Value::Bool(any.data.first().map_or(false, |b| *b != 0)),
+        (Class::Universal, Tag::Null) => Value::Null,
+        (Class::Universal, Tag::Integer) => integer_to_cbor(&any.data),
+        (Class::Universal, Tag::Utf8String) => {
+            Value::Text(String::from_utf8_lossy(&any.data).into_owned())
+        }
+        (Class::Universal, Tag::OctetString) => Value::Bytes(any.data.to_vec()),
+        (Class::Universal, Tag::Oid) => {
+            Value::Tag(OID_TAG, Box::new(Value::Text(crate::gser::oid_to_dotted(&any.data))))
+        }
+        (Class::Universal, Tag::Sequence) => Value::Array(children_to_cbor(any)),
+        (Class::Universal, Tag::Set) => Value::Tag(SET_TAG, Box::new(Value::Array(children_to_cbor(any)))),
+        (class, tag) => {
+            let inner = Value::Bytes(any.data.to_vec());
+            let tagged = Value::Array(vec![
+                Value::Integer(class as i128),
+                Value::Bool(any.header.constructed()),
+                Value::Integer(tag.0 as i128),
+                inner,
+            ]);
+            Value::Tag(TAG_PRESERVE_TAG, Box::new(tagged))
+        }
+    }
+}
+
+fn children_to_cbor(any: &Any) -> Vec<Value> {
+    let mut out = Vec::new();
+    for child in crate::SequenceIterator::<Any, crate::DerParser>::new(&any.data) {
+        match child {
+            Ok(child) => out.push(any_to_cbor(&child)),
+            Err(_) => {
+                // Malformed trailing content: surface it explicitly instead
+                // of silently truncating the array with no indication
+                // anything was wrong.
+                out.push(Value::Text("<malformed ASN.1 element>".to_string()));
+                break;
+            }
+        }
+    }
+    out
+}
+
+fn integer_to_cbor(content: &[u8]) -> Value {
+    if content.len() <= 8 {
+        let negative = !content.is_empty() && content[0] & 0x80 != 0;
+        let mut buf = [if negative { 0xff } else { 0x00 }; 8];
+        let offset = 8 - content.len();
+        buf[offset..].copy_from_slice(content);
+        return Value::Integer(i64::from_be_bytes(buf) as i128);
+    }
+    // RFC 8949 §3.4.3: values that do not fit in a CBOR major-type-0/1
+    // integer are carried as a tagged bignum byte string instead of being
+    // truncated. Tag 2 (unsigned bignum) only holds non-negative
+    // magnitudes, so a negative DER INTEGER must go through tag 3
+    // (negative bignum, `value = -1 - n`) with its two's-complement
+    // content converted to the `n` a compliant CBOR reader expects;
+    // reusing tag 2 for a negative value would silently misrepresent it
+    // as a huge positive number to any standard CBOR consumer.
+    if content[0] & 0x80 != 0 {
+        let magnitude = twos_complement_magnitude(content);
+        let n = subtract_one(&magnitude);
+        Value::Tag(NEGATIVE_BIGNUM_TAG, Box::new(Value::Bytes(n)))
+    } else {
+        let mut bytes = content.to_vec();
+        while bytes.len() > 1 && bytes[0] == 0 {
+            bytes.remove(0);
+        }
+        Value::Tag(BIGNUM_TAG, Box::new(Value::Bytes(bytes)))
+    }
+}
+
+/// RFC 8949 §3.4.3 bignum tags.
+const BIGNUM_TAG: u64 = 2;
+const NEGATIVE_BIGNUM_TAG: u64 = 3;
+
+/// Given the big-endian two's-complement content of a negative DER
+/// INTEGER, return its magnitude (`|v|`) as minimal big-endian unsigned
+/// bytes: invert every bit, add one, then strip any leading zero bytes
+/// the inversion introduced.
+fn twos_complement_magnitude(content: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = content.iter().map(|b| !b).collect();
+    add_one(&mut bytes);
+    while bytes.len() > 1 && bytes[0] == 0 {
+        bytes.remove(0);
+    }
+    bytes
+}
+
+/// Add one to a big-endian unsigned byte vector in place, growing it by a
+/// byte on overflow.
+fn add_one(bytes: &mut Vec<u8>) {
+    for byte in bytes.iter_mut().rev() {
+        if *byte == 0xff {
+            *byte = 0;
+        } else {
+            *byte += 1;
+            return;
+        }
+    }
+    bytes.insert(0, 1);
+}
+
+/// Subtract one from a big-endian unsigned byte vector, returning the
+/// minimal (no leading zero) result.
+fn subtract_one(bytes: &[u8]) -> Vec<u8> {
+    let mut out = bytes.to_vec();
+    for byte in out.iter_mut().rev() {
+        if *byte == 0 {
+            *byte = 0xff;
+        } else {
+            *byte -= 1;
+            break;
+        }
+    }
+    while out.len() > 1 && out[0] == 0 {
+        out.remove(0);
+    }
+    out
+}
+
+/// Inverse of [`twos_complement_magnitude`]/[`subtract_one`]: given a
+/// positive magnitude `m >= 1`, return the minimal big-endian two's-
+/// complement DER INTEGER content for `-m`.
+fn negate_magnitude(magnitude: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(magnitude.len() + 1);
+    bytes.push(0);
+    bytes.extend_from_slice(magnitude);
+    for byte in bytes.iter_mut() {
+        *byte = !*byte;
+    }
+    add_one(&mut bytes);
+    while bytes.len() > 1 {
+        let redundant_ff = bytes[0] == 0xff && bytes[1] & 0x80 != 0;
+        if redundant_ff {
+            bytes.remove(0);
+        } else {
+            break;
+        }
+    }
+    bytes
+}
+
+/// Convert a CBOR value produced by [`any_to_cbor`] (or structured the same
+/// way) back into DER bytes.
+pub fn cbor_to_der(value: &Value) -> SerializeResult<Vec<u8>> {
+    match value {
+        Value::Bool(b) => write_tlv(Class::Universal, false, Tag::Boolean, vec![if *b { 0xff } else { 0x00 }]),
+        Value::Null => write_tlv(Class::Universal, false, Tag::Null, vec![]),
+        Value::Integer(i) => write_tlv(Class::Universal, false, Tag::Integer, der_integer_bytes(*i)),
+        Value::Text(s) => write_tlv(Class::Universal, false, Tag::Utf8String, s.clone().into_bytes()),
+        Value::Bytes(b) => write_tlv(Class::Universal, false, Tag::OctetString, b.clone()),
+        Value::Array(items) => {
+            let mut content = Vec::new();
+            for item in items {
+                content.extend_from_slice(&cbor_to_der(item)?);
+            }
+            write_tlv(Class::Universal, true, Tag::Sequence, content)
+        }
+        Value::Tag(OID_TAG, inner) => match inner.as_ref() {
+            Value::Text(dotted) => {
+                let content = crate::gser::dotted_to_oid(dotted).map_err(Into::into)?;
+                write_tlv(Class::Universal, false, Tag::Oid, content)
+            }
+            _ => Err(Error::invalid_value(Tag::Oid, "expected a text string inside OID_TAG".to_string()).into()),
+        },
+        Value::Tag(SET_TAG, inner) => match inner.as_ref() {
+            Value::Array(items) => {
+                let mut content = Vec::new();
+                for item in items {
+                    content.extend_from_slice(&cbor_to_der(item)?);
+                }
+                write_tlv(Class::Universal, true, Tag::Set, content)
+            }
+            _ => Err(Error::invalid_value(Tag::Set, "expected an array inside SET_TAG".to_string()).into()),
+        },
+        Value::Tag(TAG_PRESERVE_TAG, inner) => match inner.as_ref() {
+            Value::Array(parts) if parts.len() == 4 => {
+                let class = match &parts[0] {
+                    Value::Integer(n) if (0..=3).contains(n) => {
+                        Class::try_from(*n as u8).map_err(Into::into)?
+                    }
+                    _ => {
+                        return Err(Error::invalid_value(
+                            Tag(0),
+                            "expected a class integer in TAG_PRESERVE_TAG".to_string(),
+                        )
+                        .into())
+                    }
+                };
+                let constructed = match &parts[1] {
+                    Value::Bool(b) => *b,
+                    _ => {
+                        return Err(Error::invalid_value(
+                            Tag(0),
+                            "expected a constructed bool in TAG_PRESERVE_TAG".to_string(),
+                        )
+                        .into())
+                    }
+                };
+                let tag = match &parts[2] {
+                    Value::Integer(n) if (0..=u32::MAX as i128).contains(n) => Tag(*n as u32),
+                    _ => {
+                        return Err(Error::invalid_value(
+                            Tag(0),
+                            "expected a tag number integer in TAG_PRESERVE_TAG".to_string(),
+                        )
+                        .into())
+                    }
+                };
+                let content = match &parts[3] {
+                    Value::Bytes(b) => b.clone(),
+                    _ => {
+                        return Err(Error::invalid_value(
+                            tag,
+                            "expected a byte string in TAG_PRESERVE_TAG".to_string(),
+                        )
+                        .into())
+                    }
+                };
+                write_tlv(class, constructed, tag, content)
+            }
+            _ => Err(Error::invalid_value(
+                Tag(0),
+                "expected a 4-element array inside TAG_PRESERVE_TAG".to_string(),
+            )
+            .into()),
+        },
+        Value::Tag(BIGNUM_TAG, inner) => match inner.as_ref() {
+            // Unsigned bignum (RFC 8949 §3.4.3): `n`, read as an unsigned
+            // big-endian integer, is the value itself. A leading 0x00 byte
+            // must be restored if the magnitude's high bit is set, so the
+            // DER encoding is not misread as negative.
+            Value::Bytes(b) => {
+                let mut content = b.clone();
+                if content.first().map_or(false, |first| first & 0x80 != 0) {
+                    content.insert(0, 0);
+                }
+                write_tlv(Class::Universal, false, Tag::Integer, content)
+            }
+            _ => Err(Error::invalid_value(Tag::Integer, "expected a byte string in bignum tag 2".to_string()).into()),
+        },
+        Value::Tag(NEGATIVE_BIGNUM_TAG, inner) => match inner.as_ref() {
+            // Negative bignum (RFC 8949 §3.4.3): `n`, read as an unsigned
+            // big-endian integer, represents the value `-1 - n`.
+            Value::Bytes(n) => {
+                let mut magnitude = n.clone();
+                add_one(&mut magnitude);
+                let content = negate_magnitude(&magnitude);
+                write_tlv(Class::Universal, false, Tag::Integer, content)
+            }
+            _ => Err(Error::invalid_value(Tag::Integer, "expected a byte string in bignum tag 3".to_string()).into()),
+        },
+        _ => Err(Error::invalid_value(Tag(0), "unrecognized CBOR value shape".to_string()).into()),
+    }
+}
+
+fn write_tlv(class: Class, constructed: bool, tag: Tag, content: Vec<u8>) -> SerializeResult<Vec<u8>> {
+    let header = Header::new(
+        class,
+        constructed as u8,
+        tag,
+        Length::Definite(content.len()),
+    );
+    let mut out = Vec::new();
+    header.write_der_header(&mut out)?;
+    out.extend_from_slice(&content);
+    Ok(out)
+}
+
+fn der_integer_bytes(v: i128) -> Vec<u8> {
+    if v == 0 {
+        return vec![0];
+    }
+    // Minimal big-endian two's-complement form (X.690 §8.3), derived the
+    // same way for any magnitude that fits in i128; true bignums wider than
+    // that go through the `Value::Tag(2 | 3, ..)` bignum paths instead.
+    let mut bytes = v.to_be_bytes().to_vec();
+    while bytes.len() > 1 {
+        let redundant_zero = bytes[0] == 0 && bytes[1] & 0x80 == 0;
+        let redundant_ff = bytes[0] == 0xff && bytes[1] & 0x80 != 0;
+        if redundant_zero || redundant_ff {
+            bytes.remove(0);
+        } else {
+            break;
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(der: &[u8]) {
+        let (_, any) = Any::from_der(der).expect("from_der");
+        let value = any_to_cbor(&any);
+        let reencoded = cbor_to_der(&value).expect("cbor_to_der");
+        assert_eq!(der, &reencoded[..], "der -> cbor ({:?}) -> der mismatch", value);
+    }
+
+    #[test]
+    fn roundtrip_small_integer() {
+        // INTEGER -1
+        roundtrip(&[0x02, 0x01, 0xff]);
+    }
+
+    #[test]
+    fn roundtrip_positive_bignum() {
+        // INTEGER too wide for i64, high bit clear (positive).
+        roundtrip(&[
+            0x02, 0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ]);
+    }
+
+    #[test]
+    fn roundtrip_negative_bignum() {
+        // INTEGER too wide for i64, high bit set (negative); must round-trip
+        // through CBOR tag 3, not be misread as a huge positive number.
+        roundtrip(&[
+            0x02, 0x09, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ]);
+    }
+
+    #[test]
+    fn negative_bignum_is_tagged_as_negative() {
+        let content = [0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        match integer_to_cbor(&content) {
+            Value::Tag(NEGATIVE_BIGNUM_TAG, _) => {}
+            other => panic!("expected negative bignum tag, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn roundtrip_sequence() {
+        // SEQUENCE { INTEGER 1, Utf8String "hi" }
+        roundtrip(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x0c, 0x02, b'h', b'i']);
+    }
+
+    #[test]
+    fn roundtrip_unmapped_primitive_tag_preserves_class() {
+        // BOOLEAN is mapped, but a context-specific primitive with the same
+        // tag number is not, so it takes the TAG_PRESERVE_TAG path.
+        roundtrip(&[0x81, 0x01, 0xff]);
+    }
+
+    #[test]
+    fn roundtrip_unmapped_constructed_tag_preserves_constructed_bit() {
+        // A constructed, context-specific implicit tag (e.g. the CHOICE
+        // tags `serde::Serializer` emits), which must come back
+        // constructed rather than flattened to primitive.
+        roundtrip(&[0xa0, 0x03, 0x02, 0x01, 0x01]);
+    }
+}
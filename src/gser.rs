@@ -0,0 +1,584 @@
+//! Generic String Encoding Rules (GSER, RFC 3641): a human-readable text
+//! notation for ASN.1 values that round-trips losslessly to DER.
+//!
+//! This is the same idea as Preserves' binary/textual duality, applied to
+//! values this crate already knows how to parse: [`to_gser`] prints a
+//! parsed [`Any`] tree to a readable string, and [`from_gser`] parses that
+//! string back into DER bytes. For the value shapes RFC 3641 defines as
+//! canonical (SEQUENCE, `Utf8String`, OCTET STRING, OID, INTEGER), the
+//! round trip `der -> gser -> der` is byte-identical. A SET prints with the
+//! same `{ ... }` notation as a SEQUENCE and is read back as a SEQUENCE, so
+//! it does not round-trip byte-identically. For anything else
+//! (implicit tags GSER has no notation for) values fall back to a
+//! `<class>.<constructed>.<tagnum>:value` form that is readable and parses
+//! back, but is not part of RFC 3641 proper.
+//!
+//! ```text
+//! { 1, "hello", ''48656C6C6F''H }   // SEQUENCE { INTEGER, Utf8String, OCTET STRING }
+//! ```
+
+use crate::{Any, Class, Error, Header, Length, Result, Tag, ToDer};
+use std::convert::TryFrom;
+use std::fmt::Write as _;
+
+/// Render a parsed value as a GSER text string.
+///
+/// SEQUENCE/SET print as `{ value, value }` (component names are not part
+/// of the DER encoding, so GSER identifiers are not recovered); strings
+/// print as a double-quoted, `""`-escaped UTF-8 string; OCTET STRING
+/// prints as `''<hex>''H`; OBJECT IDENTIFIER prints in dotted form;
+/// INTEGER prints in decimal, with no bound on width. Anything else falls
+/// back to `<class>.<constructed>.<tagnum>:''<hex>''H`, preserving the
+/// original class and constructed bit (not just the tag number) so the
+/// round trip is exact even for a Universal-class type this module does
+/// not special-case, or a constructed implicit tag.
+///
+/// Fails if a SEQUENCE/SET child does not itself parse as DER, so a
+/// malformed element is reported rather than silently truncating the
+/// printed string.
+pub fn to_gser(any: &Any) -> Result<String> {
+    let mut out = String::new();
+    write_gser(any, &mut out)?;
+    Ok(out)
+}
+
+fn write_gser(any: &Any, out: &mut String) -> Result<()> {
+    match (any.header.class(), any.tag()) {
+        (Class::Universal, Tag::Sequence) | (Class::Universal, Tag::Set) => {
+            out.push_str("{ ");
+            let mut first = true;
+            for child in crate::SequenceIterator::<Any, crate::DerParser>::new(&any.data) {
+                let child = child?;
+                if !first {
+                    out.push_str(", ");
+                }
+                first = false;
+                write_gser(&child, out)?;
+            }
+            out.push_str(" }");
+        }
+        (Class::Universal, Tag::Utf8String) => {
+            out.push('"');
+            let s = String::from_utf8_lossy(&any.data);
+            for c in s.chars() {
+                if c == '"' {
+                    out.push_str("\"\"");
+                } else {
+                    out.push(c);
+                }
+            }
+            out.push('"');
+        }
+        (Class::Universal, Tag::OctetString) => {
+            out.push_str("''");
+            for byte in any.data.iter() {
+                let _ = write!(out, "{:02X}", byte);
+            }
+            out.push_str("''H");
+        }
+        (Class::Universal, Tag::Oid) => {
+            // The dotted form is exactly what this crate's OID type already
+            // produces via `Display`; reparsing it back to DER content on
+            // the way in is the inverse of that formatting.
+            out.push_str(&oid_to_dotted(&any.data));
+        }
+        (Class::Universal, Tag::Integer) => {
+            out.push_str(&integer_to_decimal(&any.data));
+        }
+        _ => {
+            let _ = write!(
+                out,
+                "{}.{}.{}:",
+                any.header.class() as u8,
+                any.header.constructed() as u8,
+                any.tag().0
+            );
+            out.push_str("''");
+            for byte in any.data.iter() {
+                let _ = write!(out, "{:02X}", byte);
+            }
+            out.push_str("''H");
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn oid_to_dotted(content: &[u8]) -> String {
+    // X.690 §8.19 base-128 OID encoding.
+    let mut arcs = Vec::new();
+    if let Some((&first, rest)) = content.split_first() {
+        arcs.push((first / 40) as u64);
+        arcs.push((first % 40) as u64);
+        let mut value: u64 = 0;
+        for &byte in rest {
+            value = (value << 7) | (byte & 0x7f) as u64;
+            if byte & 0x80 == 0 {
+                arcs.push(value);
+                value = 0;
+            }
+        }
+    }
+    arcs.iter()
+        .map(|a| a.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+pub(crate) fn dotted_to_oid(dotted: &str) -> Result<Vec<u8>> {
+    let arcs: Vec<u64> = dotted
+        .split('.')
+        .map(|a| {
+            a.parse::<u64>()
+                .map_err(|_| Error::invalid_value(Tag::Oid, "invalid OID arc".to_string()))
+        })
+        .collect::<Result<_>>()?;
+    if arcs.len() < 2 {
+        return Err(Error::invalid_value(
+            Tag::Oid,
+            "OID must have at least two arcs".to_string(),
+        ));
+    }
+    let mut content = vec![(arcs[0] * 40 + arcs[1]) as u8];
+    for &arc in &arcs[2..] {
+        let mut bytes = vec![(arc & 0x7f) as u8];
+        let mut value = arc >> 7;
+        while value > 0 {
+            bytes.push(((value & 0x7f) as u8) | 0x80);
+            value >>= 7;
+        }
+        bytes.reverse();
+        content.extend_from_slice(&bytes);
+    }
+    Ok(content)
+}
+
+/// Render DER INTEGER content as a plain decimal string, with no bound on
+/// width: GSER itself places none, and `from_gser`'s grammar only ever
+/// accepts `[0-9-]+`, so this must not fall back to any other notation
+/// (e.g. a `0x...` hex dump) that the parser side cannot also consume.
+fn integer_to_decimal(content: &[u8]) -> String {
+    if content.is_empty() {
+        return "0".to_string();
+    }
+    let negative = content[0] & 0x80 != 0;
+    let magnitude = if negative {
+        twos_complement_magnitude(content)
+    } else {
+        let mut bytes = content.to_vec();
+        while bytes.len() > 1 && bytes[0] == 0 {
+            bytes.remove(0);
+        }
+        bytes
+    };
+    let digits = magnitude_to_decimal(magnitude);
+    if negative {
+        format!("-{}", digits)
+    } else {
+        digits
+    }
+}
+
+/// Add one to a big-endian unsigned byte vector in place, growing it by a
+/// byte on overflow.
+fn add_one(bytes: &mut Vec<u8>) {
+    for byte in bytes.iter_mut().rev() {
+        if *byte == 0xff {
+            *byte = 0;
+        } else {
+            *byte += 1;
+            return;
+        }
+    }
+    bytes.insert(0, 1);
+}
+
+/// Given the big-endian two's-complement content of a negative DER
+/// INTEGER, return its magnitude (`|v|`) as minimal big-endian unsigned
+/// bytes: invert every bit, add one, then strip any leading zero bytes
+/// the inversion introduced.
+fn twos_complement_magnitude(content: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = content.iter().map(|b| !b).collect();
+    add_one(&mut bytes);
+    while bytes.len() > 1 && bytes[0] == 0 {
+        bytes.remove(0);
+    }
+    bytes
+}
+
+/// Inverse of [`twos_complement_magnitude`]: given a positive magnitude
+/// `m >= 1`, return the minimal big-endian two's-complement DER INTEGER
+/// content for `-m`.
+fn negate_magnitude(magnitude: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(magnitude.len() + 1);
+    bytes.push(0);
+    bytes.extend_from_slice(magnitude);
+    for byte in bytes.iter_mut() {
+        *byte = !*byte;
+    }
+    add_one(&mut bytes);
+    while bytes.len() > 1 {
+        let redundant_ff = bytes[0] == 0xff && bytes[1] & 0x80 != 0;
+        if redundant_ff {
+            bytes.remove(0);
+        } else {
+            break;
+        }
+    }
+    bytes
+}
+
+/// Render a big-endian unsigned magnitude as a decimal string, by
+/// repeatedly dividing by 10 (binary-coded-decimal-style long division).
+fn magnitude_to_decimal(mut magnitude: Vec<u8>) -> String {
+    if magnitude.iter().all(|&b| b == 0) {
+        return "0".to_string();
+    }
+    let mut digits = Vec::new();
+    while !(magnitude.len() == 1 && magnitude[0] == 0) {
+        let mut remainder: u32 = 0;
+        for byte in magnitude.iter_mut() {
+            let acc = (remainder << 8) | (*byte as u32);
+            *byte = (acc / 10) as u8;
+            remainder = acc % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+        while magnitude.len() > 1 && magnitude[0] == 0 {
+            magnitude.remove(0);
+        }
+    }
+    digits.reverse();
+    String::from_utf8(digits).expect("ASCII digits")
+}
+
+/// Inverse of [`magnitude_to_decimal`]: parse a run of decimal digits into
+/// a big-endian unsigned magnitude, by repeated multiply-by-10-and-add.
+fn decimal_to_magnitude(digits: &str) -> Vec<u8> {
+    let mut magnitude: Vec<u8> = vec![0];
+    for c in digits.chars() {
+        let d = c.to_digit(10).expect("caller validated ASCII digits") as u32;
+        let mut carry = d;
+        for byte in magnitude.iter_mut().rev() {
+            let acc = (*byte as u32) * 10 + carry;
+            *byte = (acc & 0xff) as u8;
+            carry = acc >> 8;
+        }
+        while carry > 0 {
+            magnitude.insert(0, (carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    magnitude
+}
+
+/// Parse a GSER text string back to DER bytes.
+///
+/// This is the inverse of [`to_gser`] for the canonical subset of GSER
+/// notation this module emits: `{ ... }` for SEQUENCE, a quoted string for
+/// `Utf8String`, `''...''H` for OCTET STRING, dotted decimal for OID, plain
+/// decimal for INTEGER, and `<class>.<constructed>.<tagnum>:''...''H` for
+/// the fallback case.
+pub fn from_gser(s: &str) -> Result<Vec<u8>> {
+    let (rest, bytes) = parse_value(s.trim())?;
+    if !rest.trim().is_empty() {
+        return Err(Error::invalid_value(
+            Tag(0),
+            "trailing characters after GSER value".to_string(),
+        ));
+    }
+    Ok(bytes)
+}
+
+fn parse_value(s: &str) -> Result<(&str, Vec<u8>)> {
+    let s = s.trim_start();
+    if let Some(rest) = s.strip_prefix('{') {
+        return parse_sequence(rest);
+    }
+    if let Some(rest) = s.strip_prefix('"') {
+        return parse_utf8_string(rest);
+    }
+    if s.starts_with("''") {
+        return parse_octet_string(&s[2..]);
+    }
+    if let Some(colon_pos) = s.find(':') {
+        let header = &s[..colon_pos];
+        if !header.is_empty() && header.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return parse_tagged_fallback(s, colon_pos);
+        }
+    }
+    if s.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+        let token_end = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+            .unwrap_or(s.len());
+        if s[..token_end].contains('.') {
+            return parse_oid(s);
+        }
+        return parse_integer(s);
+    }
+    Err(Error::invalid_value(Tag(0), "unrecognized GSER syntax".to_string()))
+}
+
+fn write_tlv(class: Class, constructed: bool, tag: Tag, content: Vec<u8>) -> Result<Vec<u8>> {
+    let header = Header::new(
+        class,
+        constructed as u8,
+        tag,
+        Length::Definite(content.len()),
+    );
+    let mut out = Vec::new();
+    header.write_der_header(&mut out)?;
+    out.extend_from_slice(&content);
+    Ok(out)
+}
+
+fn parse_sequence(mut s: &str) -> Result<(&str, Vec<u8>)> {
+    let mut content = Vec::new();
+    s = s.trim_start();
+    if let Some(rest) = s.strip_prefix('}') {
+        return Ok((rest, write_tlv(Class::Universal, true, Tag::Sequence, content)?));
+    }
+    loop {
+        let (rest, value) = parse_value(s)?;
+        content.extend_from_slice(&value);
+        s = rest.trim_start();
+        if let Some(rest) = s.strip_prefix(',') {
+            s = rest;
+            continue;
+        }
+        if let Some(rest) = s.strip_prefix('}') {
+            s = rest;
+            break;
+        }
+        return Err(Error::invalid_value(
+            Tag::Sequence,
+            "expected ',' or '}' in GSER sequence".to_string(),
+        ));
+    }
+    Ok((s, write_tlv(Class::Universal, true, Tag::Sequence, content)?))
+}
+
+fn parse_utf8_string(s: &str) -> Result<(&str, Vec<u8>)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '"' {
+            if chars.peek().map(|(_, c)| *c) == Some('"') {
+                value.push('"');
+                chars.next();
+                continue;
+            }
+            let rest = &s[i + 1..];
+            return Ok((
+                rest,
+                write_tlv(Class::Universal, false, Tag::Utf8String, value.into_bytes())?,
+            ));
+        }
+        value.push(c);
+    }
+    Err(Error::invalid_value(
+        Tag::Utf8String,
+        "unterminated GSER string".to_string(),
+    ))
+}
+
+fn parse_octet_string(s: &str) -> Result<(&str, Vec<u8>)> {
+    let end = s.find("''").ok_or_else(|| {
+        Error::invalid_value(
+            Tag::OctetString,
+            "missing closing '' in GSER octet string".to_string(),
+        )
+    })?;
+    let hex = &s[..end];
+    let rest = &s[end + 2..];
+    let rest = rest.strip_prefix('H').ok_or_else(|| {
+        Error::invalid_value(
+            Tag::OctetString,
+            "expected 'H' after GSER octet string".to_string(),
+        )
+    })?;
+    let bytes = hex_to_bytes(hex)?;
+    Ok((rest, write_tlv(Class::Universal, false, Tag::OctetString, bytes)?))
+}
+
+fn hex_to_bytes(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return Err(Error::invalid_value(
+            Tag::OctetString,
+            "odd number of hex digits".to_string(),
+        ));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| {
+                Error::invalid_value(Tag::OctetString, "invalid hex digit".to_string())
+            })
+        })
+        .collect()
+}
+
+fn parse_oid(s: &str) -> Result<(&str, Vec<u8>)> {
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (dotted, rest) = s.split_at(end);
+    let content = dotted_to_oid(dotted)?;
+    Ok((rest, write_tlv(Class::Universal, false, Tag::Oid, content)?))
+}
+
+fn parse_integer(s: &str) -> Result<(&str, Vec<u8>)> {
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '-'))
+        .unwrap_or(s.len());
+    let (token, rest) = s.split_at(end);
+    let negative = token.starts_with('-');
+    let digits = if negative { &token[1..] } else { token };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::invalid_value(
+            Tag::Integer,
+            "invalid GSER integer".to_string(),
+        ));
+    }
+    let magnitude = decimal_to_magnitude(digits);
+    let is_zero = magnitude.iter().all(|&b| b == 0);
+    let content = if is_zero {
+        vec![0]
+    } else if negative {
+        negate_magnitude(&magnitude)
+    } else {
+        let mut bytes = magnitude;
+        while bytes.len() > 1 && bytes[0] == 0 {
+            bytes.remove(0);
+        }
+        if bytes[0] & 0x80 != 0 {
+            bytes.insert(0, 0);
+        }
+        bytes
+    };
+    Ok((rest, write_tlv(Class::Universal, false, Tag::Integer, content)?))
+}
+
+/// Parse the `<class>.<constructed>.<tagnum>` header of a fallback-form
+/// value, preserving all three rather than just the tag number, so the
+/// round trip is exact for any Universal-class type this module does not
+/// special-case (BOOLEAN, NULL, BIT STRING, ...) and for constructed
+/// implicit tags (e.g. the CHOICE tags `serde::Serializer` emits).
+fn parse_tagged_fallback(s: &str, colon_pos: usize) -> Result<(&str, Vec<u8>)> {
+    let header = &s[..colon_pos];
+    let mut parts = header.splitn(3, '.');
+    let invalid_header = || {
+        Error::invalid_value(
+            Tag(0),
+            "expected '<class>.<constructed>.<tagnum>' in GSER fallback form".to_string(),
+        )
+    };
+    let class_num: u8 = parts.next().ok_or_else(invalid_header)?.parse().map_err(|_| invalid_header())?;
+    let constructed_num: u8 = parts.next().ok_or_else(invalid_header)?.parse().map_err(|_| invalid_header())?;
+    let tagnum: u32 = parts.next().ok_or_else(invalid_header)?.parse().map_err(|_| invalid_header())?;
+    if parts.next().is_some() {
+        return Err(invalid_header());
+    }
+    let class = Class::try_from(class_num).map_err(Into::<Error>::into)?;
+    let constructed = constructed_num != 0;
+
+    let rest = &s[colon_pos + 1..];
+    let rest = rest.strip_prefix("''").ok_or_else(|| {
+        Error::invalid_value(Tag(tagnum), "expected '' after tag number".to_string())
+    })?;
+    let end = rest.find("''").ok_or_else(|| {
+        Error::invalid_value(
+            Tag(tagnum),
+            "missing closing '' in GSER fallback value".to_string(),
+        )
+    })?;
+    let bytes = hex_to_bytes(&rest[..end])?;
+    let rest = &rest[end + 2..];
+    let rest = rest.strip_prefix('H').ok_or_else(|| {
+        Error::invalid_value(Tag(tagnum), "expected 'H' after GSER fallback value".to_string())
+    })?;
+    Ok((rest, write_tlv(class, constructed, Tag(tagnum), bytes)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(der: &[u8]) {
+        let (_, any) = Any::from_der(der).expect("from_der");
+        let text = to_gser(&any).expect("to_gser");
+        let reencoded = from_gser(&text).expect("from_gser");
+        assert_eq!(der, &reencoded[..], "der -> gser ({}) -> der mismatch", text);
+    }
+
+    #[test]
+    fn roundtrip_integer() {
+        // INTEGER 65535
+        roundtrip(&[0x02, 0x03, 0x00, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn roundtrip_negative_integer() {
+        // INTEGER -65536
+        roundtrip(&[0x02, 0x03, 0xff, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn roundtrip_wide_integer() {
+        // INTEGER spanning more than 8 content bytes, both positive and
+        // negative, to exercise the arbitrary-precision decimal path
+        // rather than the (removed) "0x..." hex fallback.
+        roundtrip(&[
+            0x02, 0x0a, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a,
+        ]);
+        roundtrip(&[
+            0x02, 0x0a, 0xfe, 0xfd, 0xfc, 0xfb, 0xfa, 0xf9, 0xf8, 0xf7, 0xf6, 0xf5,
+        ]);
+    }
+
+    #[test]
+    fn roundtrip_utf8_string() {
+        // Utf8String "hi"
+        roundtrip(&[0x0c, 0x02, b'h', b'i']);
+    }
+
+    #[test]
+    fn roundtrip_octet_string() {
+        // OCTET STRING DE AD BE EF
+        roundtrip(&[0x04, 0x04, 0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn roundtrip_sequence() {
+        // SEQUENCE { INTEGER 1, Utf8String "hi" }
+        roundtrip(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x0c, 0x02, b'h', b'i']);
+    }
+
+    #[test]
+    fn roundtrip_fallback_preserves_universal_class() {
+        // BOOLEAN TRUE: a Universal-class primitive this module doesn't
+        // special-case, so it takes the fallback path. Regression test for
+        // a bug where the fallback only encoded the tag number, so this
+        // came back as a context-specific primitive instead of a Universal
+        // BOOLEAN.
+        roundtrip(&[0x01, 0x01, 0xff]);
+    }
+
+    #[test]
+    fn roundtrip_fallback_preserves_constructed_bit() {
+        // A constructed, context-specific implicit tag (e.g. what a CHOICE
+        // emits), which also takes the fallback path and must come back
+        // constructed rather than flattened to primitive.
+        roundtrip(&[0xa0, 0x03, 0x02, 0x01, 0x01]);
+    }
+
+    #[test]
+    fn to_gser_errors_on_malformed_sequence_child() {
+        // SEQUENCE content claims an INTEGER child of length 5 but only
+        // supplies 1 content byte within the outer SEQUENCE's own declared
+        // length: the child fails to parse, so `to_gser` must report that
+        // rather than silently returning a truncated-but-well-formed-
+        // looking string.
+        let der: &[u8] = &[0x30, 0x03, 0x02, 0x05, 0x01];
+        let (_, any) = Any::from_der(der).expect("from_der");
+        assert!(to_gser(&any).is_err());
+    }
+}
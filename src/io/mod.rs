@@ -0,0 +1,24 @@
+//! Incremental/streaming decoding over `std::io::Read`.
+//!
+//! The rest of this crate (`Sequence::parse`, `ber_iter`/`der_iter`, and
+//! `SequenceIterator`) requires a fully-buffered `&[u8]`/`Cow<[u8]>`. This
+//! module adds a reader-based alternative, analogous to the
+//! `Read`/`IoRead`/`SliceRead` split in `serde_cbor`: [`Source`] abstracts
+//! "give me the next N bytes" over both byte slices and `std::io::Read`,
+//! and [`StreamReader`] pulls one TLV header at a time and exposes its
+//! content either as a byte stream or, for constructed elements, as a lazy
+//! iterator over child elements. This lets callers parse multi-megabyte
+//! `SEQUENCE OF` values (e.g. large certificate bundles) without loading
+//! the whole encoding into memory, and supports indefinite-length BER
+//! where the total size is not known up front.
+//!
+//! `Sequence`'s slice-backed API is not re-implemented on top of this
+//! module: it remains the fast path for already-buffered input, while
+//! `StreamReader` is the path for input that should not, or cannot, be
+//! buffered whole.
+
+mod source;
+mod stream_reader;
+
+pub use source::{IoRead, Reference, SliceRead, Source};
+pub use stream_reader::{StreamIterator, StreamReader};
@@ -0,0 +1,173 @@
+use crate::{Error, Header, Result};
+
+/// A byte string obtained from a [`Source`], either borrowed straight out
+/// of the underlying buffer (lifetime `'b`) or copied into a caller-owned
+/// scratch buffer (lifetime `'c`) because the source had no stable buffer
+/// to borrow from.
+///
+/// Mirrors the `Reference` type `serde_json`/`serde_cbor` use for the same
+/// slice-vs-reader distinction.
+#[derive(Debug)]
+pub enum Reference<'b, 'c> {
+    Borrowed(&'b [u8]),
+    Copied(&'c [u8]),
+}
+
+impl<'b, 'c> AsRef<[u8]> for Reference<'b, 'c> {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Reference::Borrowed(b) => b,
+            Reference::Copied(c) => c,
+        }
+    }
+}
+
+/// Abstracts "give me the next N bytes" over both an in-memory slice and a
+/// `std::io::Read`, so [`StreamReader`](super::StreamReader) can be built
+/// once and used with either.
+pub trait Source<'de> {
+    /// Read a single BER/DER TLV header from the current position,
+    /// advancing past it.
+    fn read_header(&mut self) -> Result<Header>;
+
+    /// Read exactly `len` content bytes, advancing past them.
+    ///
+    /// A slice-backed source can return a reference borrowed directly from
+    /// its buffer (`'de`); a reader-backed source must copy into
+    /// `scratch` and return a reference borrowed from that instead, since
+    /// bytes pulled from a `Read` have no lifetime beyond this call.
+    fn read_bytes<'s>(
+        &'s mut self,
+        len: usize,
+        scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'de, 's>>;
+
+    /// Skip `len` content bytes without retaining them (used to discard
+    /// elements the caller is not interested in).
+    fn skip_bytes(&mut self, len: usize) -> Result<()>;
+
+    /// Number of bytes consumed from the underlying source so far.
+    fn position(&self) -> u64;
+}
+
+/// A [`Source`] backed by an in-memory byte slice.
+///
+/// This is the zero-copy case: `read_bytes` always returns
+/// `Reference::Borrowed`.
+pub struct SliceRead<'de> {
+    slice: &'de [u8],
+    pos: u64,
+}
+
+impl<'de> SliceRead<'de> {
+    pub fn new(slice: &'de [u8]) -> Self {
+        SliceRead { slice, pos: 0 }
+    }
+}
+
+impl<'de> Source<'de> for SliceRead<'de> {
+    fn read_header(&mut self) -> Result<Header> {
+        let (rem, header) = Header::parse_ber(self.slice).map_err(Into::<Error>::into)?;
+        self.pos += (self.slice.len() - rem.len()) as u64;
+        self.slice = rem;
+        Ok(header)
+    }
+
+    fn read_bytes<'s>(
+        &'s mut self,
+        len: usize,
+        _scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'de, 's>> {
+        if self.slice.len() < len {
+            return Err(Error::InvalidLength);
+        }
+        let (content, rem) = self.slice.split_at(len);
+        self.slice = rem;
+        self.pos += len as u64;
+        Ok(Reference::Borrowed(content))
+    }
+
+    fn skip_bytes(&mut self, len: usize) -> Result<()> {
+        if self.slice.len() < len {
+            return Err(Error::InvalidLength);
+        }
+        self.slice = &self.slice[len..];
+        self.pos += len as u64;
+        Ok(())
+    }
+
+    fn position(&self) -> u64 {
+        self.pos
+    }
+}
+
+/// A [`Source`] backed by any `std::io::Read`.
+///
+/// Unlike [`SliceRead`], this has no stable buffer to borrow from, so every
+/// `read_bytes` call copies into the caller-supplied `scratch` buffer and
+/// returns `Reference::Copied`.
+pub struct IoRead<R> {
+    reader: R,
+    pos: u64,
+}
+
+impl<R: std::io::Read> IoRead<R> {
+    pub fn new(reader: R) -> Self {
+        IoRead { reader, pos: 0 }
+    }
+}
+
+impl<'de, R: std::io::Read> Source<'de> for IoRead<R> {
+    fn read_header(&mut self) -> Result<Header> {
+        // Headers are at most a handful of bytes; read incrementally until
+        // the header parser stops reporting "incomplete".
+        let mut buf = Vec::with_capacity(16);
+        loop {
+            let mut byte = [0u8; 1];
+            self.reader
+                .read_exact(&mut byte)
+                .map_err(|_| Error::InvalidLength)?;
+            buf.push(byte[0]);
+            match Header::parse_ber(&buf) {
+                Ok((_rem, header)) => {
+                    self.pos += buf.len() as u64;
+                    return Ok(header);
+                }
+                Err(nom::Err::Incomplete(_)) => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    fn read_bytes<'s>(
+        &'s mut self,
+        len: usize,
+        scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'de, 's>> {
+        scratch.clear();
+        scratch.resize(len, 0);
+        self.reader
+            .read_exact(scratch)
+            .map_err(|_| Error::InvalidLength)?;
+        self.pos += len as u64;
+        Ok(Reference::Copied(scratch))
+    }
+
+    fn skip_bytes(&mut self, len: usize) -> Result<()> {
+        let mut remaining = len;
+        let mut buf = [0u8; 4096];
+        while remaining > 0 {
+            let n = remaining.min(buf.len());
+            self.reader
+                .read_exact(&mut buf[..n])
+                .map_err(|_| Error::InvalidLength)?;
+            remaining -= n;
+        }
+        self.pos += len as u64;
+        Ok(())
+    }
+
+    fn position(&self) -> u64 {
+        self.pos
+    }
+}
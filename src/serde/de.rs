@@ -0,0 +1,564 @@
+use super::{SerdeError, SerdeResult};
+use crate::{Any, Class, FromDer, SequenceIterator, Tag};
+use serde::de::{
+    self, DeserializeSeed, Deserializer as _, EnumAccess, IntoDeserializer, SeqAccess,
+    VariantAccess, Visitor,
+};
+use std::convert::TryFrom;
+
+/// Deserialize `T` from a byte slice of DER bytes produced by
+/// [`to_der_vec`](super::to_der_vec).
+///
+/// The bytes are decoded directly as `T`, the inverse of `to_der_vec`: no
+/// extra SEQUENCE wrapper is expected around a scalar top-level value.
+pub fn from_der_slice<'b, T>(bytes: &'b [u8]) -> SerdeResult<T>
+where
+    T: de::DeserializeOwned,
+{
+    let mut deserializer = Deserializer { input: bytes };
+    T::deserialize(&mut deserializer)
+}
+
+/// A `serde::Deserializer` that reads DER bytes, driving the existing
+/// [`FromDer`] machinery (via [`Any`]) and [`SequenceIterator`] to walk
+/// constructed content. See [`Serializer`](super::Serializer) for the
+/// field-ordering and CHOICE-tagging convention this relies on.
+///
+/// `Deserializer` never hands `serde` a reference that is required to
+/// outlive the call that produced it (every `visit_*` call here copies:
+/// `visit_str`/`visit_bytes`, never `visit_borrowed_str`/
+/// `visit_borrowed_bytes`). That is what lets the `serde::Deserializer<'de>`
+/// impl below stay generic over `'de` independently of `'b`, the lifetime
+/// of the buffer this particular `Deserializer` happens to borrow: nested
+/// values (sequence elements, map entries, enum payloads) are decoded from
+/// a short-lived, stack-owned `Vec<u8>` produced by re-encoding the child
+/// element, with a `Deserializer` borrowing *that* buffer for the duration
+/// of the recursive call only. No buffer is ever leaked to manufacture a
+/// lifetime, unlike a naive implementation might do with `Box::leak`.
+pub struct Deserializer<'b> {
+    input: &'b [u8],
+}
+
+impl<'b> Deserializer<'b> {
+    fn next_any(&mut self) -> SerdeResult<Any<'b>> {
+        let (rem, any) = Any::from_der(self.input).map_err(|e| SerdeError::Asn1(e.into()))?;
+        self.input = rem;
+        Ok(any)
+    }
+}
+
+fn der_integer_to_i64(bytes: &[u8]) -> SerdeResult<i64> {
+    if bytes.is_empty() {
+        return Err(SerdeError::Message("empty INTEGER content".to_string()));
+    }
+    if bytes.len() > 8 {
+        return Err(SerdeError::Message(
+            "INTEGER too large for i64; use a bignum-aware type".to_string(),
+        ));
+    }
+    let negative = bytes[0] & 0x80 != 0;
+    let mut buf = [if negative { 0xff } else { 0x00 }; 8];
+    let offset = 8 - bytes.len();
+    buf[offset..].copy_from_slice(bytes);
+    Ok(i64::from_be_bytes(buf))
+}
+
+/// Re-encode `any` and deserialize `seed` from the result, with a
+/// `Deserializer` scoped to that local buffer. This is how every nested
+/// value (sequence element, map key/value, enum payload) is decoded,
+/// without requiring the child's encoding to outlive the parent.
+fn deserialize_nested<'de, S>(seed: S, any: &Any) -> SerdeResult<S::Value>
+where
+    S: DeserializeSeed<'de>,
+{
+    let bytes = crate::ToDer::to_der_vec(any).map_err(|e| SerdeError::Asn1(e.into()))?;
+    let mut sub = Deserializer { input: &bytes };
+    seed.deserialize(&mut sub)
+}
+
+impl<'b, 'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'b> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        match any.tag() {
+            Tag::Boolean => visitor.visit_bool(!any.data.is_empty() && any.data[0] != 0),
+            Tag::Integer => visitor.visit_i64(der_integer_to_i64(&any.data)?),
+            Tag::Utf8String => {
+                let s = std::str::from_utf8(&any.data)
+                    .map_err(|e| SerdeError::Message(e.to_string()))?;
+                visitor.visit_str(s)
+            }
+            Tag::OctetString => visitor.visit_bytes(&any.data),
+            Tag::Null => visitor.visit_unit(),
+            Tag::Sequence => {
+                let iter = SequenceIterator::<Any, crate::DerParser>::new(&any.data);
+                visitor.visit_seq(SeqWalker { iter })
+            }
+            _ => Err(SerdeError::Message(format!(
+                "unsupported or unexpected tag {:?} while deserializing",
+                any.tag()
+            ))),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Integer).map_err(SerdeError::from)?;
+        visitor.visit_i64(der_integer_to_i64(&any.data)?)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Integer).map_err(SerdeError::from)?;
+        let v = der_integer_to_i64(&any.data)?;
+        if v < 0 {
+            return Err(SerdeError::Message(
+                "INTEGER content is negative; cannot deserialize as unsigned".to_string(),
+            ));
+        }
+        visitor.visit_u64(v as u64)
+    }
+
+    fn deserialize_f32<V>(self, _visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(SerdeError::Message(
+            "REAL (floating point) DER decoding is not implemented".to_string(),
+        ))
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f32(visitor)
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Utf8String).map_err(SerdeError::from)?;
+        let s = std::str::from_utf8(&any.data).map_err(|e| SerdeError::Message(e.to_string()))?;
+        visitor.visit_str(s)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::OctetString).map_err(SerdeError::from)?;
+        visitor.visit_bytes(&any.data)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // Peek at the next tag without consuming it: NULL means `None`,
+        // anything else is forwarded as `Some`.
+        let (_, any) = Any::from_der(self.input).map_err(|e| SerdeError::Asn1(e.into()))?;
+        if any.tag() == Tag::Null {
+            self.next_any()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Null).map_err(SerdeError::from)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Sequence).map_err(SerdeError::from)?;
+        let iter = SequenceIterator::<Any, crate::DerParser>::new(&any.data);
+        visitor.visit_seq(SeqWalker { iter })
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Sequence).map_err(SerdeError::from)?;
+        let iter = SequenceIterator::<Any, crate::DerParser>::new(&any.data);
+        visitor.visit_map(MapWalker {
+            iter,
+            pending_value: None,
+        })
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        if any.header.class() != Class::ContextSpecific {
+            return Err(SerdeError::Message(
+                "expected a context-specific tagged CHOICE alternative".to_string(),
+            ));
+        }
+        visitor.visit_enum(EnumWalker { any })
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.next_any()?;
+        visitor.visit_unit()
+    }
+}
+
+/// Walks the DER-encoded elements of a SEQUENCE, handing each one back to
+/// `serde` via [`deserialize_nested`].
+///
+/// Generic over `'b` (the lifetime of the buffer `iter` borrows from) and,
+/// on its `SeqAccess` impl, over `'de` independently: every element is
+/// decoded through a re-encoded, stack-owned buffer scoped to that single
+/// call, so no reference here needs to live as long as the top-level
+/// `'de`.
+struct SeqWalker<'b> {
+    iter: SequenceIterator<'b, Any<'b>, crate::DerParser>,
+}
+
+impl<'b, 'de> SeqAccess<'de> for SeqWalker<'b> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> SerdeResult<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            None => Ok(None),
+            Some(Err(e)) => Err(SerdeError::Asn1(e)),
+            Some(Ok(any)) => deserialize_nested(seed, &any).map(Some),
+        }
+    }
+}
+
+/// Walks a SEQUENCE of `{ key, value }` pairs, the convention used by
+/// [`Serializer`](super::Serializer) to encode maps.
+struct MapWalker<'b> {
+    iter: SequenceIterator<'b, Any<'b>, crate::DerParser>,
+    pending_value: Option<Any<'b>>,
+}
+
+impl<'b, 'de> de::MapAccess<'de> for MapWalker<'b> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> SerdeResult<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            None => Ok(None),
+            Some(Err(e)) => Err(SerdeError::Asn1(e)),
+            Some(Ok(pair)) => {
+                let mut inner = SequenceIterator::<Any, crate::DerParser>::new(&pair.data);
+                let key = inner
+                    .next()
+                    .ok_or_else(|| SerdeError::Message("map entry missing key".to_string()))?
+                    .map_err(SerdeError::Asn1)?;
+                let value = deserialize_nested(seed, &key)?;
+                self.pending_value = Some(pair);
+                Ok(Some(value))
+            }
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> SerdeResult<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let pair = self
+            .pending_value
+            .take()
+            .ok_or_else(|| SerdeError::Message("next_value called before next_key".to_string()))?;
+        let mut inner = SequenceIterator::<Any, crate::DerParser>::new(&pair.data);
+        let _key = inner.next();
+        let value = inner
+            .next()
+            .ok_or_else(|| SerdeError::Message("map entry missing value".to_string()))?
+            .map_err(SerdeError::Asn1)?;
+        deserialize_nested(seed, &value)
+    }
+}
+
+struct EnumWalker<'b> {
+    any: Any<'b>,
+}
+
+impl<'b, 'de> EnumAccess<'de> for EnumWalker<'b> {
+    type Error = SerdeError;
+    type Variant = VariantWalker<'b>;
+
+    fn variant_seed<V>(self, seed: V) -> SerdeResult<(V::Value, Self::Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let index = self.any.tag().0;
+        let value = seed.deserialize(index.into_deserializer())?;
+        Ok((value, VariantWalker { any: self.any }))
+    }
+}
+
+struct VariantWalker<'b> {
+    any: Any<'b>,
+}
+
+impl<'b, 'de> VariantAccess<'de> for VariantWalker<'b> {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> SerdeResult<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> SerdeResult<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        // The payload's own TLV sits inside the alternative's tag; decode
+        // that inner element, not the tagged wrapper.
+        let (_, inner) = Any::from_der(&self.any.data).map_err(|e| SerdeError::Asn1(e.into()))?;
+        deserialize_nested(seed, &inner)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let iter = SequenceIterator::<Any, crate::DerParser>::new(&self.any.data);
+        visitor.visit_seq(SeqWalker { iter })
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> SerdeResult<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let iter = SequenceIterator::<Any, crate::DerParser>::new(&self.any.data);
+        visitor.visit_seq(SeqWalker { iter })
+    }
+}
+
+// Ensure `TryFrom<Any>` stays linked in for callers that also use the plain
+// `FromDer`/`TryFrom<Any>` machinery alongside this module.
+#[allow(unused_imports)]
+use crate::Tagged;
+#[allow(dead_code)]
+fn _assert_try_from<'a, T: TryFrom<Any<'a>>>() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Point {
+        x: i64,
+        y: i64,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Shape {
+        Circle(i64),
+        Point,
+    }
+
+    #[test]
+    fn roundtrip_struct() {
+        let point = Point { x: 1, y: -2 };
+        let bytes = super::super::to_der_vec(&point).expect("to_der_vec");
+        let decoded: Point = from_der_slice(&bytes).expect("from_der_slice");
+        assert_eq!(point, decoded);
+    }
+
+    #[test]
+    fn roundtrip_vec() {
+        let values: Vec<i64> = vec![1, 2, 3];
+        let bytes = super::super::to_der_vec(&values).expect("to_der_vec");
+        let decoded: Vec<i64> = from_der_slice(&bytes).expect("from_der_slice");
+        assert_eq!(values, decoded);
+    }
+
+    #[test]
+    fn roundtrip_enum_newtype_variant() {
+        let shape = Shape::Circle(5);
+        let bytes = super::super::to_der_vec(&shape).expect("to_der_vec");
+        let decoded: Shape = from_der_slice(&bytes).expect("from_der_slice");
+        assert_eq!(shape, decoded);
+    }
+
+    #[test]
+    fn roundtrip_many_nested_values() {
+        // Exercises the `deserialize_nested` path (sequence elements) many
+        // times over; only the round trip is checked, memory use is not
+        // measured.
+        let values: Vec<i64> = (0..1000).collect();
+        let bytes = super::super::to_der_vec(&values).expect("to_der_vec");
+        let decoded: Vec<i64> = from_der_slice(&bytes).expect("from_der_slice");
+        assert_eq!(values, decoded);
+    }
+}
@@ -0,0 +1,71 @@
+//! Optional `serde` integration.
+//!
+//! This module lets ordinary Rust types that derive `serde::Serialize` and
+//! `serde::Deserialize` be encoded to and decoded from DER without writing
+//! any ASN.1-specific code by hand. It is modeled after the way
+//! `serde_cbor` layers a `Serializer`/`Deserializer` pair on top of CBOR's
+//! data model: Rust structs become a SEQUENCE of their fields (in
+//! declaration order), `Vec`/slices become a SEQUENCE OF using the existing
+//! [`Sequence::from_iter_to_der`](crate::Sequence::from_iter_to_der) path,
+//! enums become a CHOICE tagged with the variant index as a
+//! context-specific tag (explicit for newtype variants, implicit
+//! otherwise), `String` uses the [`Utf8String`](crate::Utf8String)
+//! impl, and byte buffers become OCTET STRING.
+//!
+//! Only compiled when the `serde` feature is enabled.
+
+mod de;
+mod ser;
+
+pub use de::{from_der_slice, Deserializer};
+pub use ser::{to_der_vec, Serializer};
+
+use crate::Error;
+use std::fmt;
+
+/// Error type returned by the `serde` integration.
+///
+/// Wraps [`crate::Error`] so it can also satisfy `serde::ser::Error` and
+/// `serde::de::Error`, which require constructing an error from an
+/// arbitrary `Display` message (e.g. from a failed field validation in
+/// user code).
+#[derive(Debug)]
+pub enum SerdeError {
+    /// An error produced by the DER encoder/decoder itself.
+    Asn1(Error),
+    /// An error message produced by `serde` or by the type being
+    /// (de)serialized.
+    Message(String),
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerdeError::Asn1(e) => write!(f, "{}", e),
+            SerdeError::Message(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl From<Error> for SerdeError {
+    fn from(e: Error) -> Self {
+        SerdeError::Asn1(e)
+    }
+}
+
+impl serde::ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError::Message(msg.to_string())
+    }
+}
+
+impl serde::de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError::Message(msg.to_string())
+    }
+}
+
+/// Convenience alias for results returned by this module.
+pub type SerdeResult<T> = std::result::Result<T, SerdeError>;
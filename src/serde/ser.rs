@@ -0,0 +1,516 @@
+use super::{SerdeError, SerdeResult};
+use crate::traits::*;
+use crate::{Class, Header, Length, Result, Sequence, SerializeResult, Tag};
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+
+/// Serialize `value` to a `Vec<u8>` of DER bytes.
+///
+/// The output is one TLV for `value` itself, mirroring the way
+/// `serde_cbor::to_vec` emits one CBOR item: a struct becomes the SEQUENCE
+/// of its fields and a sequence the SEQUENCE of its elements, while a
+/// scalar is emitted as its own element with no SEQUENCE around it.
+pub fn to_der_vec<T>(value: &T) -> SerdeResult<Vec<u8>>
+where
+    T: Serialize,
+{
+    let mut ser = Serializer { output: Vec::new() };
+    value.serialize(&mut ser)?;
+    Ok(ser.output)
+}
+
+/// A `serde::Serializer` that writes its input as DER-encoded bytes.
+///
+/// Field names are not encoded (ASN.1 has no concept of them): the
+/// canonical, documented convention used by this module is that struct
+/// fields are emitted as SEQUENCE components in declaration order, and enum
+/// variants are emitted as a CHOICE using a context-specific tag equal to
+/// the variant's declaration index: implicit for unit, tuple and struct
+/// variants, explicit (wrapping the payload's own TLV) for newtype
+/// variants. Decoding with
+/// [`from_der_slice`](super::from_der_slice) relies on this same convention,
+/// so round-tripping requires the Rust type definition to stay stable.
+pub struct Serializer {
+    output: Vec<u8>,
+}
+
+fn write_tlv(class: Class, constructed: bool, tag: Tag, content: &[u8]) -> SerdeResult<Vec<u8>> {
+    let header = Header::new(
+        class,
+        constructed as u8,
+        tag,
+        Length::Definite(content.len()),
+    );
+    let mut out = Vec::new();
+    header
+        .write_der_header(&mut out)
+        .map_err(|e| SerdeError::Asn1(e.into()))?;
+    out.extend_from_slice(content);
+    Ok(out)
+}
+
+/// A component that has already been DER-encoded by this serializer, kept
+/// only so [`Sequence::from_iter_to_der`] (which wants a `ToDer + Tagged`
+/// item) can be reused to assemble and TLV-wrap a SEQUENCE of
+/// already-encoded components, instead of re-implementing that
+/// concatenation-and-wrap step here. Its `TAG` is never consulted by
+/// `from_iter_to_der` — only its encoded bytes are.
+struct Encoded(Vec<u8>);
+
+impl Tagged for Encoded {
+    const TAG: Tag = Tag::Sequence;
+}
+
+impl ToDer for Encoded {
+    fn to_der_len(&self) -> Result<usize> {
+        Ok(self.0.len())
+    }
+
+    fn write_der_header(&self, _writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        Ok(0)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(&self.0).map_err(Into::into)
+    }
+}
+
+fn wrap_sequence(items: &[Vec<u8>]) -> SerdeResult<Vec<u8>> {
+    let sequence = Sequence::from_iter_to_der(items.iter().cloned().map(Encoded))
+        .map_err(|e| SerdeError::Asn1(e.into()))?;
+    sequence
+        .to_der_vec()
+        .map_err(|e| SerdeError::Asn1(e.into()))
+}
+
+/// Context-specific tag used for a CHOICE alternative (an enum variant),
+/// keyed by the variant's declaration index.
+fn variant_tag(variant_index: u32) -> Tag {
+    Tag(variant_index as u32)
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = SerdeError;
+
+    type SerializeSeq = CollectSeq<'a>;
+    type SerializeTuple = CollectSeq<'a>;
+    type SerializeTupleStruct = CollectSeq<'a>;
+    type SerializeTupleVariant = CollectVariant<'a>;
+    type SerializeMap = CollectMap<'a>;
+    type SerializeStruct = CollectSeq<'a>;
+    type SerializeStructVariant = CollectVariant<'a>;
+
+    fn serialize_bool(self, v: bool) -> SerdeResult<()> {
+        // DER requires TRUE to be encoded as 0xFF (X.690 §11.1).
+        let content = [if v { 0xff } else { 0x00 }];
+        self.output
+            .extend(write_tlv(Class::Universal, false, Tag::Boolean, &content)?);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> SerdeResult<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> SerdeResult<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> SerdeResult<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> SerdeResult<()> {
+        self.output
+            .extend(write_tlv(Class::Universal, false, Tag::Integer, &der_integer_bytes(v))?);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> SerdeResult<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u16(self, v: u16) -> SerdeResult<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u32(self, v: u32) -> SerdeResult<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u64(self, v: u64) -> SerdeResult<()> {
+        // DER INTEGER has no fixed width; encode as a minimal big-endian
+        // two's-complement value, adding a leading zero byte if needed to
+        // keep it non-negative.
+        let mut bytes = v.to_be_bytes().to_vec();
+        while bytes.len() > 1 && bytes[0] == 0 && bytes[1] < 0x80 {
+            bytes.remove(0);
+        }
+        if bytes[0] & 0x80 != 0 {
+            bytes.insert(0, 0);
+        }
+        self.output
+            .extend(write_tlv(Class::Universal, false, Tag::Integer, &bytes)?);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> SerdeResult<()> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, _v: f64) -> SerdeResult<()> {
+        Err(SerdeError::Message(
+            "REAL (floating point) DER encoding is not implemented".to_string(),
+        ))
+    }
+
+    fn serialize_char(self, v: char) -> SerdeResult<()> {
+        let mut buf = [0u8; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
+    }
+
+    fn serialize_str(self, v: &str) -> SerdeResult<()> {
+        self.output.extend(write_tlv(
+            Class::Universal,
+            false,
+            Tag::Utf8String,
+            v.as_bytes(),
+        )?);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> SerdeResult<()> {
+        self.output
+            .extend(write_tlv(Class::Universal, false, Tag::OctetString, v)?);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> SerdeResult<()> {
+        self.output
+            .extend(write_tlv(Class::Universal, false, Tag::Null, &[])?);
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> SerdeResult<()> {
+        self.output
+            .extend(write_tlv(Class::Universal, false, Tag::Null, &[])?);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> SerdeResult<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> SerdeResult<()> {
+        self.output
+            .extend(write_tlv(Class::ContextSpecific, false, variant_tag(variant_index), &[])?);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut inner = Serializer { output: Vec::new() };
+        value.serialize(&mut inner)?;
+        self.output.extend(write_tlv(
+            Class::ContextSpecific,
+            true,
+            variant_tag(variant_index),
+            &inner.output,
+        )?);
+        Ok(())
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> SerdeResult<Self::SerializeSeq> {
+        Ok(CollectSeq {
+            parent: self,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> SerdeResult<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> SerdeResult<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> SerdeResult<Self::SerializeTupleVariant> {
+        Ok(CollectVariant {
+            parent: self,
+            tag: variant_tag(variant_index),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> SerdeResult<Self::SerializeMap> {
+        Ok(CollectMap {
+            parent: self,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> SerdeResult<Self::SerializeStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> SerdeResult<Self::SerializeStructVariant> {
+        Ok(CollectVariant {
+            parent: self,
+            tag: variant_tag(variant_index),
+            items: Vec::with_capacity(len),
+        })
+    }
+}
+
+/// DER-encode a signed integer as a minimal big-endian two's-complement
+/// byte string (X.690 §8.3).
+fn der_integer_bytes(v: i64) -> Vec<u8> {
+    if v == 0 {
+        return vec![0];
+    }
+    let mut bytes = v.to_be_bytes().to_vec();
+    while bytes.len() > 1 {
+        let keep_leading_zero = bytes[0] == 0 && bytes[1] & 0x80 != 0;
+        let keep_leading_ff = bytes[0] == 0xff && bytes[1] & 0x80 == 0;
+        if keep_leading_zero || keep_leading_ff {
+            break;
+        }
+        if bytes[0] == 0x00 || bytes[0] == 0xff {
+            bytes.remove(0);
+        } else {
+            break;
+        }
+    }
+    bytes
+}
+
+fn serialize_to_buf<T: ?Sized + Serialize>(value: &T) -> SerdeResult<Vec<u8>> {
+    let mut ser = Serializer { output: Vec::new() };
+    value.serialize(&mut ser)?;
+    Ok(ser.output)
+}
+
+/// Accumulates SEQUENCE / SEQUENCE OF components (used for seqs, tuples and
+/// structs, which all map onto a SEQUENCE).
+pub struct CollectSeq<'a> {
+    parent: &'a mut Serializer,
+    items: Vec<Vec<u8>>,
+}
+
+impl<'a> SerializeSeq for CollectSeq<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.items.push(serialize_to_buf(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> SerdeResult<()> {
+        self.parent.output.extend(wrap_sequence(&self.items)?);
+        Ok(())
+    }
+}
+
+impl<'a> SerializeTuple for CollectSeq<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> SerdeResult<()> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a> SerializeTupleStruct for CollectSeq<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> SerdeResult<()> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a> SerializeStruct for CollectSeq<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        // Field names are intentionally dropped: fields are positional in
+        // the encoded SEQUENCE, matching the convention documented on
+        // `Serializer`.
+        self.items.push(serialize_to_buf(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> SerdeResult<()> {
+        self.parent.output.extend(wrap_sequence(&self.items)?);
+        Ok(())
+    }
+}
+
+/// Accumulates the inner content of a CHOICE variant (tuple or struct
+/// variants), which is wrapped in an implicit, context-specific tag.
+pub struct CollectVariant<'a> {
+    parent: &'a mut Serializer,
+    tag: Tag,
+    items: Vec<Vec<u8>>,
+}
+
+impl<'a> SerializeTupleVariant for CollectVariant<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.items.push(serialize_to_buf(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> SerdeResult<()> {
+        let mut content = Vec::new();
+        for item in &self.items {
+            content.extend_from_slice(item);
+        }
+        self.parent
+            .output
+            .extend(write_tlv(Class::ContextSpecific, true, self.tag, &content)?);
+        Ok(())
+    }
+}
+
+impl<'a> SerializeStructVariant for CollectVariant<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.items.push(serialize_to_buf(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> SerdeResult<()> {
+        let mut content = Vec::new();
+        for item in &self.items {
+            content.extend_from_slice(item);
+        }
+        self.parent
+            .output
+            .extend(write_tlv(Class::ContextSpecific, true, self.tag, &content)?);
+        Ok(())
+    }
+}
+
+/// Maps are encoded as a SEQUENCE of `{ key, value }` pairs (each pair
+/// itself a two-element SEQUENCE), since DER has no canonical map type.
+pub struct CollectMap<'a> {
+    parent: &'a mut Serializer,
+    items: Vec<Vec<u8>>,
+    key: Option<Vec<u8>>,
+}
+
+impl<'a> SerializeMap for CollectMap<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.key = Some(serialize_to_buf(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> SerdeResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        let value = serialize_to_buf(value)?;
+        self.items.push(wrap_sequence(&[key, value])?);
+        Ok(())
+    }
+
+    fn end(self) -> SerdeResult<()> {
+        self.parent.output.extend(wrap_sequence(&self.items)?);
+        Ok(())
+    }
+}